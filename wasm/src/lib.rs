@@ -7,8 +7,7 @@ pub fn init() {
     console_error_panic_hook::set_once();
 }
 
-/// High-performance image blur using box blur algorithm
-/// Much faster than canvas filter for large images
+/// High-performance image blur using a sliding-window box blur, O(1) per pixel
 #[wasm_bindgen]
 pub fn blur_image(data: &[u8], width: u32, height: u32, radius: u32) -> Vec<u8> {
     let len = data.len();
@@ -21,65 +20,126 @@ pub fn blur_image(data: &[u8], width: u32, height: u32, radius: u32) -> Vec<u8>
 
     // Horizontal pass
     for y in 0..h {
-        for x in 0..w {
-            let mut r_sum = 0u32;
-            let mut g_sum = 0u32;
-            let mut b_sum = 0u32;
-            let mut a_sum = 0u32;
-            let mut count = 0u32;
-
-            for dx in -r..=r {
-                let nx = x + dx;
-                if nx >= 0 && nx < w {
-                    let idx = ((y * w + nx) * 4) as usize;
-                    r_sum += data[idx] as u32;
-                    g_sum += data[idx + 1] as u32;
-                    b_sum += data[idx + 2] as u32;
-                    a_sum += data[idx + 3] as u32;
-                    count += 1;
-                }
+        let row = (y * w) as usize;
+        let mut r_sum = 0u32;
+        let mut g_sum = 0u32;
+        let mut b_sum = 0u32;
+        let mut a_sum = 0u32;
+        let mut count = 0u32;
+
+        for dx in -r..=r {
+            if dx >= 0 && dx < w {
+                let idx = (row + dx as usize) * 4;
+                r_sum += data[idx] as u32;
+                g_sum += data[idx + 1] as u32;
+                b_sum += data[idx + 2] as u32;
+                a_sum += data[idx + 3] as u32;
+                count += 1;
             }
+        }
 
-            let idx = ((y * w + x) * 4) as usize;
+        for x in 0..w {
+            let idx = (row + x as usize) * 4;
             temp[idx] = (r_sum / count) as u8;
             temp[idx + 1] = (g_sum / count) as u8;
             temp[idx + 2] = (b_sum / count) as u8;
             temp[idx + 3] = (a_sum / count) as u8;
+
+            let incoming = x + r + 1;
+            let outgoing = x - r;
+            if incoming < w {
+                let in_idx = (row + incoming as usize) * 4;
+                r_sum += data[in_idx] as u32;
+                g_sum += data[in_idx + 1] as u32;
+                b_sum += data[in_idx + 2] as u32;
+                a_sum += data[in_idx + 3] as u32;
+                count += 1;
+            }
+            if outgoing >= 0 {
+                let out_idx = (row + outgoing as usize) * 4;
+                r_sum -= data[out_idx] as u32;
+                g_sum -= data[out_idx + 1] as u32;
+                b_sum -= data[out_idx + 2] as u32;
+                a_sum -= data[out_idx + 3] as u32;
+                count -= 1;
+            }
         }
     }
 
     // Vertical pass
-    for y in 0..h {
-        for x in 0..w {
-            let mut r_sum = 0u32;
-            let mut g_sum = 0u32;
-            let mut b_sum = 0u32;
-            let mut a_sum = 0u32;
-            let mut count = 0u32;
-
-            for dy in -r..=r {
-                let ny = y + dy;
-                if ny >= 0 && ny < h {
-                    let idx = ((ny * w + x) * 4) as usize;
-                    r_sum += temp[idx] as u32;
-                    g_sum += temp[idx + 1] as u32;
-                    b_sum += temp[idx + 2] as u32;
-                    a_sum += temp[idx + 3] as u32;
-                    count += 1;
-                }
+    for x in 0..w {
+        let mut r_sum = 0u32;
+        let mut g_sum = 0u32;
+        let mut b_sum = 0u32;
+        let mut a_sum = 0u32;
+        let mut count = 0u32;
+
+        for dy in -r..=r {
+            if dy >= 0 && dy < h {
+                let idx = ((dy * w + x) * 4) as usize;
+                r_sum += temp[idx] as u32;
+                g_sum += temp[idx + 1] as u32;
+                b_sum += temp[idx + 2] as u32;
+                a_sum += temp[idx + 3] as u32;
+                count += 1;
             }
+        }
 
+        for y in 0..h {
             let idx = ((y * w + x) * 4) as usize;
             output[idx] = (r_sum / count) as u8;
             output[idx + 1] = (g_sum / count) as u8;
             output[idx + 2] = (b_sum / count) as u8;
             output[idx + 3] = (a_sum / count) as u8;
+
+            let incoming = y + r + 1;
+            let outgoing = y - r;
+            if incoming < h {
+                let in_idx = ((incoming * w + x) * 4) as usize;
+                r_sum += temp[in_idx] as u32;
+                g_sum += temp[in_idx + 1] as u32;
+                b_sum += temp[in_idx + 2] as u32;
+                a_sum += temp[in_idx + 3] as u32;
+                count += 1;
+            }
+            if outgoing >= 0 {
+                let out_idx = ((outgoing * w + x) * 4) as usize;
+                r_sum -= temp[out_idx] as u32;
+                g_sum -= temp[out_idx + 1] as u32;
+                b_sum -= temp[out_idx + 2] as u32;
+                a_sum -= temp[out_idx + 3] as u32;
+                count -= 1;
+            }
         }
     }
 
     output
 }
 
+/// True Gaussian blur via three successive box blurs (boxes-for-Gaussian)
+#[wasm_bindgen]
+pub fn gaussian_blur(data: &[u8], width: u32, height: u32, sigma: f64) -> Vec<u8> {
+    if sigma <= 0.0 {
+        return data.to_vec();
+    }
+
+    let ideal = (12.0 * sigma * sigma / 3.0 + 1.0).sqrt();
+    let mut box_w = ideal.floor() as i32;
+    if box_w % 2 == 0 {
+        box_w -= 1;
+    }
+    if box_w < 1 {
+        box_w = 1;
+    }
+
+    let radius = ((box_w - 1) / 2) as u32;
+    let radius_wide = ((box_w + 2 - 1) / 2) as u32;
+
+    let pass1 = blur_image(data, width, height, radius);
+    let pass2 = blur_image(&pass1, width, height, radius);
+    blur_image(&pass2, width, height, radius_wide)
+}
+
 /// Fast drop shadow generation
 #[wasm_bindgen]
 pub fn generate_shadow(
@@ -129,6 +189,90 @@ pub fn generate_shadow(
     }
 }
 
+/// Per-channel separable blend function `B(cb, cs)`, operating on the
+/// 0.0-1.0 normalized backdrop (`cb`) and source (`cs`) channel values.
+fn blend_channel(mode: u32, cb: f32, cs: f32) -> f32 {
+    match mode {
+        1 => cb * cs,                                        // multiply
+        2 => cb + cs - cb * cs,                               // screen
+        3 => {
+            // overlay
+            if cb <= 0.5 {
+                2.0 * cb * cs
+            } else {
+                1.0 - 2.0 * (1.0 - cb) * (1.0 - cs)
+            }
+        }
+        4 => cb.min(cs),                                      // darken
+        5 => cb.max(cs),                                      // lighten
+        6 => {
+            // color-dodge
+            if cs >= 1.0 {
+                1.0
+            } else {
+                (cb / (1.0 - cs)).min(1.0)
+            }
+        }
+        7 => {
+            // color-burn
+            if cs <= 0.0 {
+                0.0
+            } else {
+                1.0 - ((1.0 - cb) / cs).min(1.0)
+            }
+        }
+        _ => cs, // normal
+    }
+}
+
+/// Composite `src` over `dst` in-place (Porter-Duff source-over plus a
+/// separable blend mode).
+/// `mode`: 0 normal, 1 multiply, 2 screen, 3 overlay, 4 darken, 5 lighten,
+/// 6 color-dodge, 7 color-burn.
+#[wasm_bindgen]
+pub fn composite_layers(dst: &mut [u8], src: &[u8], mode: u32, opacity: f32) {
+    let len = dst.len().min(src.len());
+    let opacity = opacity.max(0.0).min(1.0);
+
+    for i in (0..len).step_by(4) {
+        if i + 3 >= dst.len() || i + 3 >= src.len() {
+            continue;
+        }
+
+        let cb_r = dst[i] as f32 / 255.0;
+        let cb_g = dst[i + 1] as f32 / 255.0;
+        let cb_b = dst[i + 2] as f32 / 255.0;
+        let ab = dst[i + 3] as f32 / 255.0;
+
+        let cs_r = src[i] as f32 / 255.0;
+        let cs_g = src[i + 1] as f32 / 255.0;
+        let cs_b = src[i + 2] as f32 / 255.0;
+        let as_ = (src[i + 3] as f32 / 255.0) * opacity;
+
+        let br = blend_channel(mode, cb_r, cs_r);
+        let bg = blend_channel(mode, cb_g, cs_g);
+        let bb = blend_channel(mode, cb_b, cs_b);
+
+        let ao = as_ + ab * (1.0 - as_);
+        let co_r = as_ * (1.0 - ab) * cs_r + as_ * ab * br + (1.0 - as_) * ab * cb_r;
+        let co_g = as_ * (1.0 - ab) * cs_g + as_ * ab * bg + (1.0 - as_) * ab * cb_g;
+        let co_b = as_ * (1.0 - ab) * cs_b + as_ * ab * bb + (1.0 - as_) * ab * cb_b;
+
+        // co_r/g/b are premultiplied by ao; unpremultiply before writing
+        // back, since the crate's RGBA buffers are straight alpha.
+        let (out_r, out_g, out_b) = if ao > 0.0 {
+            (co_r / ao, co_g / ao, co_b / ao)
+        } else {
+            (0.0, 0.0, 0.0)
+        };
+
+        dst[i] = (out_r * 255.0).round().max(0.0).min(255.0) as u8;
+        dst[i + 1] = (out_g * 255.0).round().max(0.0).min(255.0) as u8;
+        dst[i + 2] = (out_b * 255.0).round().max(0.0).min(255.0) as u8;
+        dst[i + 3] = (ao * 255.0).round().max(0.0).min(255.0) as u8;
+    }
+}
+
 /// Optimized flood fill algorithm
 #[wasm_bindgen]
 pub fn flood_fill(
@@ -297,14 +441,48 @@ pub fn adjust_colors(
     }
 }
 
-/// Point-in-polygon test for complex shapes
+/// Point-in-polygon test for complex shapes.
+/// `fill_rule`: 0 = even-odd (crossing count), 1 = nonzero (winding number).
 #[wasm_bindgen]
-pub fn point_in_polygon(px: f64, py: f64, vertices_x: &[f64], vertices_y: &[f64]) -> bool {
+pub fn point_in_polygon(
+    px: f64,
+    py: f64,
+    vertices_x: &[f64],
+    vertices_y: &[f64],
+    fill_rule: u32,
+) -> bool {
     let n = vertices_x.len();
     if n < 3 {
         return false;
     }
 
+    if fill_rule == 1 {
+        let mut winding = 0i32;
+        let mut j = n - 1;
+
+        for i in 0..n {
+            let xi = vertices_x[i];
+            let yi = vertices_y[i];
+            let xj = vertices_x[j];
+            let yj = vertices_y[j];
+
+            if yi <= py && py < yj {
+                let x_intersect = xi + (py - yi) * (xj - xi) / (yj - yi);
+                if x_intersect > px {
+                    winding += 1;
+                }
+            } else if yj <= py && py < yi {
+                let x_intersect = xi + (py - yi) * (xj - xi) / (yj - yi);
+                if x_intersect > px {
+                    winding -= 1;
+                }
+            }
+            j = i;
+        }
+
+        return winding != 0;
+    }
+
     let mut inside = false;
     let mut j = n - 1;
 
@@ -355,6 +533,175 @@ pub fn rotated_bounds(
     vec![min_x, min_y, max_x - min_x, max_y - min_y]
 }
 
+/// Solve the 8x8 linear system `a * h = b` via Gaussian elimination with
+/// partial pivoting. `a` is consumed (rows are the augmented matrix
+/// `[a | b]`, n x (n+1)).
+fn solve_linear_system(mut a: Vec<Vec<f64>>) -> Vec<f64> {
+    let n = a.len();
+
+    for col in 0..n {
+        let mut pivot = col;
+        for row in (col + 1)..n {
+            if a[row][col].abs() > a[pivot][col].abs() {
+                pivot = row;
+            }
+        }
+        a.swap(col, pivot);
+
+        let diag = a[col][col];
+        for row in (col + 1)..n {
+            let factor = a[row][col] / diag;
+            for k in col..=n {
+                a[row][k] -= factor * a[col][k];
+            }
+        }
+    }
+
+    let mut h = vec![0.0; n];
+    for row in (0..n).rev() {
+        let mut sum = a[row][n];
+        for k in (row + 1)..n {
+            sum -= a[row][k] * h[k];
+        }
+        h[row] = sum / a[row][row];
+    }
+    h
+}
+
+/// Solve for the 3x3 homography `H` (with `h33` fixed to 1) mapping the four
+/// `src` points to the four `dst` points, using the standard DLT equations.
+fn solve_homography(src: &[(f64, f64); 4], dst: &[(f64, f64); 4]) -> [f64; 9] {
+    let mut a = Vec::with_capacity(8);
+    for i in 0..4 {
+        let (x, y) = src[i];
+        let (u, v) = dst[i];
+        a.push(vec![x, y, 1.0, 0.0, 0.0, 0.0, -x * u, -y * u, u]);
+        a.push(vec![0.0, 0.0, 0.0, x, y, 1.0, -x * v, -y * v, v]);
+    }
+
+    let h = solve_linear_system(a);
+    [h[0], h[1], h[2], h[3], h[4], h[5], h[6], h[7], 1.0]
+}
+
+/// Invert a 3x3 matrix (row-major) via the adjugate / determinant.
+fn invert_3x3(m: &[f64; 9]) -> [f64; 9] {
+    let det = m[0] * (m[4] * m[8] - m[5] * m[7]) - m[1] * (m[3] * m[8] - m[5] * m[6])
+        + m[2] * (m[3] * m[7] - m[4] * m[6]);
+    let inv_det = if det.abs() > 1e-12 { 1.0 / det } else { 0.0 };
+
+    [
+        (m[4] * m[8] - m[5] * m[7]) * inv_det,
+        (m[2] * m[7] - m[1] * m[8]) * inv_det,
+        (m[1] * m[5] - m[2] * m[4]) * inv_det,
+        (m[5] * m[6] - m[3] * m[8]) * inv_det,
+        (m[0] * m[8] - m[2] * m[6]) * inv_det,
+        (m[2] * m[3] - m[0] * m[5]) * inv_det,
+        (m[3] * m[7] - m[4] * m[6]) * inv_det,
+        (m[1] * m[6] - m[0] * m[7]) * inv_det,
+        (m[0] * m[4] - m[1] * m[3]) * inv_det,
+    ]
+}
+
+/// Bilinearly sample `data` at `(x, y)`, returning transparent black outside
+/// the source bounds.
+fn sample_bilinear(data: &[u8], width: i32, height: i32, x: f64, y: f64) -> [u8; 4] {
+    if x < 0.0 || y < 0.0 || x > width as f64 || y > height as f64 {
+        return [0, 0, 0, 0];
+    }
+
+    let x0 = (x.floor() as i32).min(width - 1).max(0);
+    let y0 = (y.floor() as i32).min(height - 1).max(0);
+    let x1 = (x0 + 1).min(width - 1);
+    let y1 = (y0 + 1).min(height - 1);
+    let fx = x - x0 as f64;
+    let fy = y - y0 as f64;
+
+    let idx = |px: i32, py: i32| ((py * width + px) * 4) as usize;
+    let mut out = [0u8; 4];
+    for c in 0..4 {
+        let p00 = data[idx(x0, y0) + c] as f64;
+        let p10 = data[idx(x1, y0) + c] as f64;
+        let p01 = data[idx(x0, y1) + c] as f64;
+        let p11 = data[idx(x1, y1) + c] as f64;
+        let top = p00 + (p10 - p00) * fx;
+        let bottom = p01 + (p11 - p01) * fx;
+        out[c] = (top + (bottom - top) * fy).round().max(0.0).min(255.0) as u8;
+    }
+    out
+}
+
+/// Integer bounding box `[min_x, min_y, width, height]` of a destination quad
+#[wasm_bindgen]
+pub fn bounds_of_quad(dst_corners: &[f64]) -> Vec<i32> {
+    let mut min_x = f64::MAX;
+    let mut min_y = f64::MAX;
+    let mut max_x = f64::MIN;
+    let mut max_y = f64::MIN;
+
+    for i in 0..4 {
+        let x = dst_corners[i * 2];
+        let y = dst_corners[i * 2 + 1];
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+
+    let min_x = min_x.floor() as i32;
+    let min_y = min_y.floor() as i32;
+    let max_x = max_x.ceil() as i32;
+    let max_y = max_y.ceil() as i32;
+
+    vec![min_x, min_y, max_x - min_x, max_y - min_y]
+}
+
+/// Perspective (homography) warp of the image onto `dst_corners` (a
+/// flattened `[u0, v0, u1, v1, u2, v2, u3, v3]` quad)
+#[wasm_bindgen]
+pub fn warp_perspective(data: &[u8], width: u32, height: u32, dst_corners: &[f64]) -> Vec<u8> {
+    let w = width as f64;
+    let h = height as f64;
+
+    let src = [(0.0, 0.0), (w, 0.0), (w, h), (0.0, h)];
+    let dst = [
+        (dst_corners[0], dst_corners[1]),
+        (dst_corners[2], dst_corners[3]),
+        (dst_corners[4], dst_corners[5]),
+        (dst_corners[6], dst_corners[7]),
+    ];
+
+    let forward = solve_homography(&src, &dst);
+    let inverse = invert_3x3(&forward);
+
+    let bounds = bounds_of_quad(dst_corners);
+    let (min_x, min_y, out_w, out_h) = (bounds[0], bounds[1], bounds[2], bounds[3]);
+
+    let mut output = vec![0u8; (out_w * out_h * 4) as usize];
+
+    for oy in 0..out_h {
+        for ox in 0..out_w {
+            let u = (ox + min_x) as f64;
+            let v = (oy + min_y) as f64;
+
+            let denom = inverse[6] * u + inverse[7] * v + inverse[8];
+            if denom.abs() < 1e-12 {
+                continue;
+            }
+            let sx = (inverse[0] * u + inverse[1] * v + inverse[2]) / denom;
+            let sy = (inverse[3] * u + inverse[4] * v + inverse[5]) / denom;
+
+            let pixel = sample_bilinear(data, width as i32, height as i32, sx, sy);
+            let idx = ((oy * out_w + ox) * 4) as usize;
+            output[idx] = pixel[0];
+            output[idx + 1] = pixel[1];
+            output[idx + 2] = pixel[2];
+            output[idx + 3] = pixel[3];
+        }
+    }
+
+    output
+}
+
 /// Fast distance calculation for hit testing
 #[wasm_bindgen]
 pub fn line_distance(px: f64, py: f64, x1: f64, y1: f64, x2: f64, y2: f64) -> f64 {
@@ -375,6 +722,37 @@ pub fn line_distance(px: f64, py: f64, x1: f64, y1: f64, x2: f64, y2: f64) -> f6
     ((px - nearest_x).powi(2) + (py - nearest_y).powi(2)).sqrt()
 }
 
+/// Number of `[type, x, y, w, h, rotation]` fields per shape in the packed
+/// shape arrays shared by `batch_hit_test` and `rasterize_coverage`.
+const SHAPE_SIZE: usize = 6;
+
+/// Inside test for a single rectangle/ellipse shape, in the shape's local
+/// (unrotated) coordinate space. Shared by `batch_hit_test`'s hard hit test
+/// and `rasterize_coverage`'s supersampled coverage test.
+fn shape_contains(px: f64, py: f64, shape_type: i32, x: f64, y: f64, w: f64, h: f64, rotation: f64) -> bool {
+    // Transform point to shape's local coordinates
+    let cx = x + w / 2.0;
+    let cy = y + h / 2.0;
+    let cos_r = (-rotation).cos();
+    let sin_r = (-rotation).sin();
+    let local_x = (px - cx) * cos_r - (py - cy) * sin_r + w / 2.0;
+    let local_y = (px - cx) * sin_r + (py - cy) * cos_r + h / 2.0;
+
+    match shape_type {
+        0 => { // Rectangle
+            local_x >= 0.0 && local_x <= w && local_y >= 0.0 && local_y <= h
+        }
+        1 => { // Ellipse
+            let rx = w / 2.0;
+            let ry = h / 2.0;
+            let dx = local_x - rx;
+            let dy = local_y - ry;
+            (dx * dx) / (rx * rx) + (dy * dy) / (ry * ry) <= 1.0
+        }
+        _ => false
+    }
+}
+
 /// Batch hit test for multiple shapes
 #[wasm_bindgen]
 pub fn batch_hit_test(
@@ -382,8 +760,6 @@ pub fn batch_hit_test(
     shapes_data: &[f64], // [type, x, y, w, h, rotation, ...]
     shape_count: usize,
 ) -> i32 {
-    const SHAPE_SIZE: usize = 6;
-
     // Test from top (last) to bottom (first)
     for i in (0..shape_count).rev() {
         let offset = i * SHAPE_SIZE;
@@ -398,32 +774,226 @@ pub fn batch_hit_test(
         let h = shapes_data[offset + 4];
         let rotation = shapes_data[offset + 5];
 
-        // Transform point to shape's local coordinates
-        let cx = x + w / 2.0;
-        let cy = y + h / 2.0;
-        let cos_r = (-rotation).cos();
-        let sin_r = (-rotation).sin();
-        let local_x = (px - cx) * cos_r - (py - cy) * sin_r + w / 2.0;
-        let local_y = (px - cx) * sin_r + (py - cy) * cos_r + h / 2.0;
-
-        let hit = match shape_type {
-            0 => { // Rectangle
-                local_x >= 0.0 && local_x <= w && local_y >= 0.0 && local_y <= h
+        if shape_contains(px, py, shape_type, x, y, w, h, rotation) {
+            return i as i32;
+        }
+    }
+
+    -1 // No hit
+}
+
+/// Writes an 8-bit anti-aliased coverage mask for `shape_data`'s shapes by
+/// supersampling each pixel on a 4x4 sub-sample grid.
+#[wasm_bindgen]
+pub fn rasterize_coverage(
+    width: u32,
+    height: u32,
+    shape_data: &[f64],
+    shape_count: usize,
+    out: &mut [u8],
+) {
+    const GRID: i32 = 4;
+    const SAMPLES: i32 = GRID * GRID;
+
+    for y in 0..height {
+        for x in 0..width {
+            let mut hits = 0i32;
+
+            for sy in 0..GRID {
+                for sx in 0..GRID {
+                    let sample_x = x as f64 + (sx as f64 + 0.5) / GRID as f64;
+                    let sample_y = y as f64 + (sy as f64 + 0.5) / GRID as f64;
+
+                    for i in 0..shape_count {
+                        let offset = i * SHAPE_SIZE;
+                        if offset + SHAPE_SIZE > shape_data.len() {
+                            continue;
+                        }
+
+                        let shape_type = shape_data[offset] as i32;
+                        let sx_ = shape_data[offset + 1];
+                        let sy_ = shape_data[offset + 2];
+                        let sw = shape_data[offset + 3];
+                        let sh = shape_data[offset + 4];
+                        let rotation = shape_data[offset + 5];
+
+                        if shape_contains(sample_x, sample_y, shape_type, sx_, sy_, sw, sh, rotation) {
+                            hits += 1;
+                            break;
+                        }
+                    }
+                }
             }
-            1 => { // Ellipse
-                let rx = w / 2.0;
-                let ry = h / 2.0;
-                let dx = local_x - rx;
-                let dy = local_y - ry;
-                (dx * dx) / (rx * rx) + (dy * dy) / (ry * ry) <= 1.0
+
+            let idx = (y * width + x) as usize;
+            if idx < out.len() {
+                out[idx] = (255 * hits / SAMPLES) as u8;
             }
-            _ => false
+        }
+    }
+}
+
+/// Unpack a packed RGBA `u32` (as used by `generate_shadow`/`flood_fill`)
+/// into `(r, g, b, a)` channel values in 0.0-255.0.
+fn unpack_rgba(color: u32) -> (f64, f64, f64, f64) {
+    (
+        ((color >> 24) & 0xFF) as f64,
+        ((color >> 16) & 0xFF) as f64,
+        ((color >> 8) & 0xFF) as f64,
+        (color & 0xFF) as f64,
+    )
+}
+
+/// Apply a gradient spread mode to a raw (unclamped) gradient parameter `t`.
+/// `spread`: 0 = clamp (pad), 1 = repeat, 2 = reflect.
+fn apply_spread(t: f64, spread: u32) -> f64 {
+    match spread {
+        1 => t - t.floor(), // repeat
+        2 => {
+            // reflect
+            let m = t.rem_euclid(2.0);
+            if m > 1.0 {
+                2.0 - m
+            } else {
+                m
+            }
+        }
+        _ => t.max(0.0).min(1.0), // clamp
+    }
+}
+
+/// Sample a multi-stop gradient at parameter `t` (already spread-adjusted),
+/// interpolating the bracketing stops in premultiplied space.
+fn sample_gradient(t: f64, stops_pos: &[f32], stops_rgba: &[u32]) -> [u8; 4] {
+    let n = stops_pos.len().min(stops_rgba.len());
+    if n == 0 {
+        return [0, 0, 0, 0];
+    }
+
+    if n == 1 || t <= stops_pos[0] as f64 {
+        let (r, g, b, a) = unpack_rgba(stops_rgba[0]);
+        return [r as u8, g as u8, b as u8, a as u8];
+    }
+    if t >= stops_pos[n - 1] as f64 {
+        let (r, g, b, a) = unpack_rgba(stops_rgba[n - 1]);
+        return [r as u8, g as u8, b as u8, a as u8];
+    }
+
+    for i in 0..n - 1 {
+        let p0 = stops_pos[i] as f64;
+        let p1 = stops_pos[i + 1] as f64;
+        if t < p0 || t > p1 {
+            continue;
+        }
+
+        let local_t = if (p1 - p0).abs() < 1e-9 {
+            0.0
+        } else {
+            (t - p0) / (p1 - p0)
         };
 
-        if hit {
-            return i as i32;
+        let (r0, g0, b0, a0) = unpack_rgba(stops_rgba[i]);
+        let (r1, g1, b1, a1) = unpack_rgba(stops_rgba[i + 1]);
+
+        // Interpolate in premultiplied space
+        let pr = (r0 * a0 / 255.0) + ((r1 * a1 / 255.0) - (r0 * a0 / 255.0)) * local_t;
+        let pg = (g0 * a0 / 255.0) + ((g1 * a1 / 255.0) - (g0 * a0 / 255.0)) * local_t;
+        let pb = (b0 * a0 / 255.0) + ((b1 * a1 / 255.0) - (b0 * a0 / 255.0)) * local_t;
+        let a = a0 + (a1 - a0) * local_t;
+
+        let (r, g, b) = if a > 0.0 {
+            (pr * 255.0 / a, pg * 255.0 / a, pb * 255.0 / a)
+        } else {
+            (0.0, 0.0, 0.0)
+        };
+
+        return [
+            r.round().max(0.0).min(255.0) as u8,
+            g.round().max(0.0).min(255.0) as u8,
+            b.round().max(0.0).min(255.0) as u8,
+            a.round().max(0.0).min(255.0) as u8,
+        ];
+    }
+
+    [0, 0, 0, 0]
+}
+
+/// Fill `out` with a linear gradient from `(x0, y0)` to `(x1, y1)`.
+/// `spread`: 0 = clamp, 1 = repeat, 2 = reflect.
+#[wasm_bindgen]
+pub fn fill_linear_gradient(
+    out: &mut [u8],
+    width: u32,
+    height: u32,
+    x0: f64,
+    y0: f64,
+    x1: f64,
+    y1: f64,
+    spread: u32,
+    stops_pos: &[f32],
+    stops_rgba: &[u32],
+) {
+    let dx = x1 - x0;
+    let dy = y1 - y0;
+    let len_sq = dx * dx + dy * dy;
+
+    for y in 0..height {
+        for x in 0..width {
+            let px = x as f64 + 0.5;
+            let py = y as f64 + 0.5;
+
+            let raw_t = if len_sq > 0.0 {
+                ((px - x0) * dx + (py - y0) * dy) / len_sq
+            } else {
+                0.0
+            };
+            let t = apply_spread(raw_t, spread);
+            let color = sample_gradient(t, stops_pos, stops_rgba);
+
+            let idx = ((y * width + x) * 4) as usize;
+            if idx + 3 >= out.len() {
+                continue;
+            }
+            out[idx] = color[0];
+            out[idx + 1] = color[1];
+            out[idx + 2] = color[2];
+            out[idx + 3] = color[3];
         }
     }
+}
 
-    -1 // No hit
+/// Fill `out` with a radial gradient centered at `(cx, cy)` with the given
+/// `radius`. `spread`: 0 = clamp, 1 = repeat, 2 = reflect.
+#[wasm_bindgen]
+pub fn fill_radial_gradient(
+    out: &mut [u8],
+    width: u32,
+    height: u32,
+    cx: f64,
+    cy: f64,
+    radius: f64,
+    spread: u32,
+    stops_pos: &[f32],
+    stops_rgba: &[u32],
+) {
+    for y in 0..height {
+        for x in 0..width {
+            let px = x as f64 + 0.5;
+            let py = y as f64 + 0.5;
+
+            let dist = ((px - cx).powi(2) + (py - cy).powi(2)).sqrt();
+            let raw_t = if radius > 0.0 { dist / radius } else { 0.0 };
+            let t = apply_spread(raw_t, spread);
+            let color = sample_gradient(t, stops_pos, stops_rgba);
+
+            let idx = ((y * width + x) * 4) as usize;
+            if idx + 3 >= out.len() {
+                continue;
+            }
+            out[idx] = color[0];
+            out[idx + 1] = color[1];
+            out[idx + 2] = color[2];
+            out[idx + 3] = color[3];
+        }
+    }
 }